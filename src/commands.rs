@@ -0,0 +1,8 @@
+pub mod cat_file;
+pub mod clone;
+pub mod commit_tree;
+pub mod hash_object;
+pub mod init;
+pub mod ls_tree;
+pub mod pack_objects;
+pub mod write_tree;
@@ -1,19 +1,59 @@
-use crate::git_objects;
+use crate::git_objects::{self, GitObject};
 use anyhow::{Context, Result};
+use std::io::Write;
 
-pub fn run(pretty_print: bool, object_hash: String) -> Result<()> {
-    if pretty_print {
-        let content = git_objects::read_git_object(&object_hash)?;
+pub fn run(
+    pretty_print: bool,
+    show_type: bool,
+    show_size: bool,
+    object_hash: String,
+) -> Result<()> {
+    let content = git_objects::read_git_object(&object_hash)?;
 
-        // Convert to string for blob content
-        let blob_content = String::from_utf8(content).context("Invalid UTF-8 in object content")?;
+    if show_type {
+        let (object_type, _, _) = git_objects::parse_object_header(&content)?;
+        println!("{object_type}");
+        return Ok(());
+    }
 
-        // Remove header from blob content, get only real file content by splitting from the null byte
-        let file_content = blob_content
-            .split('\0')
-            .nth(1)
-            .context("Invalid object format: missing null separator")?;
-        print!("{}", file_content.trim_end());
+    if show_size {
+        let (_, size, _) = git_objects::parse_object_header(&content)?;
+        println!("{size}");
+        return Ok(());
+    }
+
+    if pretty_print {
+        match git_objects::parse_object(&content)? {
+            GitObject::Blob(body) => {
+                // Blobs may be binary, so write the raw bytes through.
+                std::io::stdout()
+                    .write_all(&body)
+                    .context("Failed to write blob content")?;
+            }
+            GitObject::Tree(entries) => {
+                git_objects::display_tree_entries(&entries, false);
+            }
+            GitObject::Commit(commit) => {
+                println!("tree {}", commit.tree);
+                for parent in &commit.parents {
+                    println!("parent {parent}");
+                }
+                println!("author {}", commit.author);
+                println!("committer {}", commit.committer);
+                println!();
+                print!("{}", commit.message);
+            }
+            GitObject::Tag(tag) => {
+                println!("object {}", tag.object);
+                println!("type {}", tag.object_type);
+                println!("tag {}", tag.name);
+                if let Some(tagger) = &tag.tagger {
+                    println!("tagger {tagger}");
+                }
+                println!();
+                print!("{}", tag.message);
+            }
+        }
     }
     Ok(())
 }
@@ -0,0 +1,18 @@
+use crate::git_objects;
+use anyhow::Result;
+
+pub fn run(url: String, directory: Option<String>) -> Result<()> {
+    // Derive the target directory from the repository name when omitted.
+    let directory = directory.unwrap_or_else(|| {
+        url.trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or("repository")
+            .trim_end_matches(".git")
+            .to_string()
+    });
+
+    git_objects::transport::clone(&url, &directory)?;
+    println!("Cloned {url} into {directory}");
+    Ok(())
+}
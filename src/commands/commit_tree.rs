@@ -2,8 +2,14 @@ use crate::git_objects;
 use anyhow::Result;
 
 pub fn run(tree_sha: String, parent_sha: Option<String>, message: String) -> Result<()> {
-    let commit_hash =
-        git_objects::create_commit_object(&tree_sha, parent_sha.as_deref(), &message)?;
+    let (author, committer) = git_objects::resolve_signatures()?;
+    let commit_hash = git_objects::create_commit_object(
+        &tree_sha,
+        parent_sha.as_deref(),
+        &message,
+        &author,
+        &committer,
+    )?;
     println!("{commit_hash}");
     Ok(())
 }
@@ -0,0 +1,71 @@
+use crate::git_objects::{self, packfile, parse_object_header, parse_tree_entries, GitObjectType};
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::io::Write;
+
+pub fn run(object_sha: String) -> Result<()> {
+    // Collect the transitive closure of objects reachable from the root.
+    let mut seen = BTreeSet::new();
+    let mut objects = Vec::new();
+    collect(&object_sha, &mut seen, &mut objects)?;
+
+    let pack = packfile::build(&objects)?;
+
+    // `pack-objects` writes the raw packfile to stdout so it can feed directly
+    // into `git-upload-pack`.
+    std::io::stdout()
+        .write_all(&pack)
+        .context("Failed to write packfile to stdout")?;
+    Ok(())
+}
+
+/// Walks commits → trees → blobs, appending every reachable object exactly once.
+fn collect(
+    sha: &str,
+    seen: &mut BTreeSet<String>,
+    objects: &mut Vec<(GitObjectType, Vec<u8>)>,
+) -> Result<()> {
+    if !seen.insert(sha.to_string()) {
+        return Ok(());
+    }
+
+    let raw = git_objects::read_git_object(sha)?;
+    let (object_type, body) = split_object(&raw)?;
+    objects.push((object_type.clone(), body.clone()));
+
+    match object_type {
+        GitObjectType::Commit => {
+            let text = std::str::from_utf8(&body).context("Invalid commit encoding")?;
+            for line in text.lines() {
+                if line.is_empty() {
+                    break; // header ends at the blank line before the message
+                }
+                if let Some(tree) = line.strip_prefix("tree ") {
+                    collect(tree, seen, objects)?;
+                } else if let Some(parent) = line.strip_prefix("parent ") {
+                    collect(parent, seen, objects)?;
+                }
+            }
+        }
+        GitObjectType::Tree => {
+            for entry in parse_tree_entries(&raw)? {
+                collect(&entry.sha1, seen, objects)?;
+            }
+        }
+        GitObjectType::Tag => {
+            let text = std::str::from_utf8(&body).context("Invalid tag encoding")?;
+            if let Some(object) = text.lines().find_map(|l| l.strip_prefix("object ")) {
+                collect(object, seen, objects)?;
+            }
+        }
+        GitObjectType::Blob => {}
+    }
+
+    Ok(())
+}
+
+/// Splits a loose object into its type and body, dropping the header.
+fn split_object(raw: &[u8]) -> Result<(GitObjectType, Vec<u8>)> {
+    let (object_type, _size, body_start) = parse_object_header(raw)?;
+    Ok((object_type, raw[body_start..].to_vec()))
+}
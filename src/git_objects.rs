@@ -10,20 +10,83 @@ use std::str::FromStr;
 
 // Declare submodules
 mod blob;
+mod commit;
+mod config;
 mod hash;
 mod path;
+pub mod packfile;
+pub mod protocol;
+mod tag;
 mod tree;
+pub mod transport;
 
 // Re-export public items
 pub use blob::create_blob_object;
+pub use commit::{create_commit_object, parse_commit, Commit, Signature};
+pub use config::resolve_signatures;
 pub use hash::{calculate_object_hash, hex_to_bytes, validate_sha1};
 pub use path::git_object_path;
-pub use tree::{display_tree_entries, parse_tree_entries, write_tree};
+pub use tag::{parse_tag, Tag};
+pub use tree::{display_tree_entries, parse_tree_entries, write_tree, TreeEntry};
+
+/// A parsed Git object, dispatched on its type header.
+#[derive(Debug)]
+pub enum GitObject {
+    Blob(Vec<u8>),
+    Tree(Vec<TreeEntry>),
+    Commit(Commit),
+    Tag(Tag),
+}
+
+impl GitObject {
+    /// Returns the object's type.
+    pub fn object_type(&self) -> GitObjectType {
+        match self {
+            GitObject::Blob(_) => GitObjectType::Blob,
+            GitObject::Tree(_) => GitObjectType::Tree,
+            GitObject::Commit(_) => GitObjectType::Commit,
+            GitObject::Tag(_) => GitObjectType::Tag,
+        }
+    }
+}
+
+/// Reads the `"<type> <size>\0"` header of a raw object, returning the type,
+/// the declared size and the offset at which the body begins.
+pub fn parse_object_header(raw: &[u8]) -> Result<(GitObjectType, usize, usize)> {
+    let null_pos = raw
+        .iter()
+        .position(|&b| b == 0)
+        .context("Invalid object: missing null separator")?;
+    let header = std::str::from_utf8(&raw[..null_pos]).context("Invalid object header")?;
+
+    let (type_str, size_str) = header
+        .split_once(' ')
+        .context("Invalid object header: missing space")?;
+    let object_type = type_str.parse::<GitObjectType>()?;
+    let size = size_str.parse().context("Invalid object size in header")?;
+
+    Ok((object_type, size, null_pos + 1))
+}
+
+/// Parses a raw object into its structured representation, dispatching on the type header.
+pub fn parse_object(raw: &[u8]) -> Result<GitObject> {
+    let (object_type, _size, body_start) = parse_object_header(raw)?;
+    let body = &raw[body_start..];
+
+    Ok(match object_type {
+        GitObjectType::Blob => GitObject::Blob(body.to_vec()),
+        GitObjectType::Tree => GitObject::Tree(parse_tree_entries(raw)?),
+        GitObjectType::Commit => GitObject::Commit(parse_commit(body)?),
+        GitObjectType::Tag => GitObject::Tag(parse_tag(body)?),
+    })
+}
 
 #[derive(Debug, Clone)]
 pub enum GitObjectType {
     Blob,
     Tree,
+    Commit,
+    Tag,
 }
 
 impl fmt::Display for GitObjectType {
@@ -31,6 +94,22 @@ impl fmt::Display for GitObjectType {
         match self {
             GitObjectType::Blob => write!(f, "blob"),
             GitObjectType::Tree => write!(f, "tree"),
+            GitObjectType::Commit => write!(f, "commit"),
+            GitObjectType::Tag => write!(f, "tag"),
+        }
+    }
+}
+
+impl FromStr for GitObjectType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blob" => Ok(GitObjectType::Blob),
+            "tree" => Ok(GitObjectType::Tree),
+            "commit" => Ok(GitObjectType::Commit),
+            "tag" => Ok(GitObjectType::Tag),
+            _ => Err(anyhow!("Unknown object type: {}", s)),
         }
     }
 }
@@ -1,19 +1,110 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::fmt;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Creates a commit object
+/// An author or committer identity with its timestamp and timezone offset.
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub name: String,
+    pub email: String,
+    pub when: u64,
+    pub tz: String,
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} <{}> {} {}", self.name, self.email, self.when, self.tz)
+    }
+}
+
+impl Signature {
+    /// Parses an identity line of the form `name <email> timestamp tz`.
+    pub fn parse(line: &str) -> Result<Self> {
+        let email_start = line.find('<').context("Signature missing '<'")?;
+        let email_end = line.find('>').context("Signature missing '>'")?;
+
+        let name = line[..email_start].trim().to_string();
+        let email = line[email_start + 1..email_end].to_string();
+
+        let mut rest = line[email_end + 1..].split_whitespace();
+        let when = rest
+            .next()
+            .context("Signature missing timestamp")?
+            .parse()
+            .context("Invalid signature timestamp")?;
+        let tz = rest.next().unwrap_or("+0000").to_string();
+
+        Ok(Signature {
+            name,
+            email,
+            when,
+            tz,
+        })
+    }
+
+    /// Builds a signature for `name`/`email` stamped with the current time and
+    /// the local timezone offset.
+    pub fn now(name: String, email: String) -> Result<Self> {
+        let when = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        Ok(Signature {
+            name,
+            email,
+            when,
+            tz: super::config::local_tz_offset(),
+        })
+    }
+}
+
+/// A parsed commit object.
+#[derive(Debug)]
+pub struct Commit {
+    pub tree: String,
+    pub parents: Vec<String>,
+    pub author: Signature,
+    pub committer: Signature,
+    pub message: String,
+}
+
+/// Parses the body of a commit object (the bytes after the `"commit <len>\0"` header).
+pub fn parse_commit(body: &[u8]) -> Result<Commit> {
+    let text = std::str::from_utf8(body).context("Invalid commit encoding")?;
+
+    let (header, message) = text.split_once("\n\n").unwrap_or((text, ""));
+
+    let mut tree = None;
+    let mut parents = Vec::new();
+    let mut author = None;
+    let mut committer = None;
+
+    for line in header.lines() {
+        if let Some(sha) = line.strip_prefix("tree ") {
+            tree = Some(sha.to_string());
+        } else if let Some(sha) = line.strip_prefix("parent ") {
+            parents.push(sha.to_string());
+        } else if let Some(sig) = line.strip_prefix("author ") {
+            author = Some(Signature::parse(sig)?);
+        } else if let Some(sig) = line.strip_prefix("committer ") {
+            committer = Some(Signature::parse(sig)?);
+        }
+    }
+
+    Ok(Commit {
+        tree: tree.context("Commit missing tree")?,
+        parents,
+        author: author.context("Commit missing author")?,
+        committer: committer.context("Commit missing committer")?,
+        message: message.to_string(),
+    })
+}
+
+/// Creates a commit object with the given author and committer identities.
 pub fn create_commit_object(
     tree_hash: &str,
     parent_hash: Option<&str>,
     message: &str,
+    author: &Signature,
+    committer: &Signature,
 ) -> Result<String> {
-    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-
-    // Default author info
-    let author_name = "Hugo";
-    let author_email = "test@example.com";
-    let timezone = "+0000";
-
     let mut commit_content = String::new();
 
     // Tree line
@@ -25,14 +116,10 @@ pub fn create_commit_object(
     }
 
     // Author line
-    commit_content.push_str(&format!(
-        "author {author_name} <{author_email}> {timestamp} {timezone}\n",
-    ));
-
-    // Committer line (same as author)
-    commit_content.push_str(&format!(
-        "committer {author_name} <{author_email}> {timestamp} {timezone}\n",
-    ));
+    commit_content.push_str(&format!("author {author}\n"));
+
+    // Committer line
+    commit_content.push_str(&format!("committer {committer}\n"));
 
     // Empty line before message
     commit_content.push('\n');
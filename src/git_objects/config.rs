@@ -0,0 +1,117 @@
+use crate::git_objects::commit::Signature;
+use anyhow::{Context, Result};
+use time::{OffsetDateTime, UtcOffset};
+
+/// Resolves the author and committer identities for a new commit.
+///
+/// Each identity is taken from the `GIT_AUTHOR_*` / `GIT_COMMITTER_*`
+/// environment variables when present, otherwise from the `user.name` /
+/// `user.email` keys of the repository `.git/config` (falling back to the
+/// user's `~/.gitconfig`).
+pub fn resolve_signatures() -> Result<(Signature, Signature)> {
+    // The config files are only a fallback, so a missing or incomplete config
+    // is not an error as long as the environment supplies the fields.
+    let (cfg_name, cfg_email) = config_identity();
+
+    let author = Signature::now(
+        field("GIT_AUTHOR_NAME", cfg_name.as_deref(), "user.name")?,
+        field("GIT_AUTHOR_EMAIL", cfg_email.as_deref(), "user.email")?,
+    )?;
+    let committer = Signature::now(
+        field("GIT_COMMITTER_NAME", cfg_name.as_deref(), "user.name")?,
+        field("GIT_COMMITTER_EMAIL", cfg_email.as_deref(), "user.email")?,
+    )?;
+
+    Ok((author, committer))
+}
+
+/// Resolves a single identity field: the environment variable takes
+/// precedence, then the config fallback, otherwise an error naming `key`.
+fn field(var: &str, fallback: Option<&str>, key: &str) -> Result<String> {
+    if let Ok(value) = std::env::var(var) {
+        if !value.is_empty() {
+            return Ok(value);
+        }
+    }
+    fallback
+        .map(str::to_string)
+        .with_context(|| format!("Missing {key}: set it via environment or git config"))
+}
+
+/// Reads `user.name` and `user.email` from `.git/config`, falling back to
+/// `~/.gitconfig`. Missing files or keys simply yield `None`.
+fn config_identity() -> (Option<String>, Option<String>) {
+    let mut name = None;
+    let mut email = None;
+
+    for path in config_files() {
+        if name.is_some() && email.is_some() {
+            break;
+        }
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let (section_name, section_email) = parse_user_section(&contents);
+        name = name.or(section_name);
+        email = email.or(section_email);
+    }
+
+    (name, email)
+}
+
+/// The config files to consult, most specific first.
+fn config_files() -> Vec<std::path::PathBuf> {
+    let mut files = vec![std::path::PathBuf::from(".git/config")];
+    if let Ok(home) = std::env::var("HOME") {
+        files.push(std::path::Path::new(&home).join(".gitconfig"));
+    }
+    files
+}
+
+/// Extracts `name` and `email` from the `[user]` section of an INI config.
+fn parse_user_section(contents: &str) -> (Option<String>, Option<String>) {
+    let mut name = None;
+    let mut email = None;
+    let mut in_user = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_user = line
+                .trim_matches(|c| c == '[' || c == ']')
+                .trim()
+                .eq_ignore_ascii_case("user");
+            continue;
+        }
+        if !in_user {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim().to_string();
+            match key.trim() {
+                "name" => name = Some(value),
+                "email" => email = Some(value),
+                _ => {}
+            }
+        }
+    }
+
+    (name, email)
+}
+
+/// Returns the local timezone offset formatted as `+HHMM` / `-HHMM`.
+///
+/// The offset is resolved in-process; it falls back to UTC (`+0000`) only when
+/// the platform cannot determine a local offset.
+pub fn local_tz_offset() -> String {
+    let offset = OffsetDateTime::now_local()
+        .map(|now| now.offset())
+        .unwrap_or(UtcOffset::UTC);
+    let total = offset.whole_seconds();
+    let sign = if total < 0 { '-' } else { '+' };
+    let abs = total.abs();
+    format!("{sign}{:02}{:02}", abs / 3600, (abs % 3600) / 60)
+}
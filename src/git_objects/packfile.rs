@@ -0,0 +1,319 @@
+use crate::git_objects::{write_git_object, GitObjectType};
+use anyhow::{bail, Context, Result};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+/// Type codes used in the packfile object header.
+const OBJ_COMMIT: u8 = 1;
+const OBJ_TREE: u8 = 2;
+const OBJ_BLOB: u8 = 3;
+const OBJ_TAG: u8 = 4;
+const OBJ_OFS_DELTA: u8 = 6;
+const OBJ_REF_DELTA: u8 = 7;
+
+/// Parses a Git packfile, resolving both delta encodings, and stores every
+/// contained object into `.git/objects` via [`write_git_object`].
+///
+/// The header is `PACK`, a 4-byte version and a 4-byte big-endian object count;
+/// the trailing 20 bytes are a SHA-1 over the rest of the stream, which is
+/// verified before unpacking. Returns each object as a `(type, body)` pair in
+/// the order it appeared in the pack.
+pub fn unpack(data: &[u8]) -> Result<Vec<(GitObjectType, Vec<u8>)>> {
+    if data.len() < 32 || &data[..4] != b"PACK" {
+        bail!("Invalid packfile signature");
+    }
+    verify_checksum(data)?;
+
+    let count = u32::from_be_bytes(data[8..12].try_into().unwrap()) as usize;
+
+    // `pos` tracks the offset of each object header so ofs-delta bases can be
+    // resolved against previously seen objects.
+    let mut pos = 12;
+    let mut by_offset: HashMap<usize, (GitObjectType, Vec<u8>)> = HashMap::new();
+    let mut by_sha: HashMap<String, (GitObjectType, Vec<u8>)> = HashMap::new();
+    let mut objects = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let start = pos;
+        let (type_code, _size, header_len) = read_object_header(&data[pos..])?;
+        pos += header_len;
+
+        let (obj_type, body) = match type_code {
+            OBJ_COMMIT | OBJ_TREE | OBJ_BLOB | OBJ_TAG => {
+                let (raw, consumed) = inflate(&data[pos..])?;
+                pos += consumed;
+                (object_type(type_code)?, raw)
+            }
+            OBJ_REF_DELTA => {
+                if data.len() < pos + 20 {
+                    bail!("Truncated packfile: missing ref-delta base SHA");
+                }
+                let base_sha: String = data[pos..pos + 20]
+                    .iter()
+                    .map(|b| format!("{b:02x}"))
+                    .collect();
+                pos += 20;
+                let (delta, consumed) = inflate(&data[pos..])?;
+                pos += consumed;
+                let (base_type, base) = by_sha
+                    .get(&base_sha)
+                    .cloned()
+                    .context("ref-delta base not found in packfile")?;
+                (base_type, apply_delta(&base, &delta)?)
+            }
+            OBJ_OFS_DELTA => {
+                let (offset, consumed) = read_offset(&data[pos..])?;
+                pos += consumed;
+                if offset > start {
+                    bail!("Invalid ofs-delta: base offset precedes packfile start");
+                }
+                let (delta, consumed) = inflate(&data[pos..])?;
+                pos += consumed;
+                let (base_type, base) = by_offset
+                    .get(&(start - offset))
+                    .cloned()
+                    .context("ofs-delta base not found in packfile")?;
+                (base_type, apply_delta(&base, &delta)?)
+            }
+            other => bail!("Unsupported packfile object type: {other}"),
+        };
+
+        let sha = store_object(&obj_type, &body)?;
+        by_offset.insert(start, (obj_type.clone(), body.clone()));
+        by_sha.insert(sha, (obj_type.clone(), body.clone()));
+        objects.push((obj_type, body));
+    }
+
+    Ok(objects)
+}
+
+/// Serializes `objects` into a valid packfile with all objects stored whole
+/// (no delta compression).
+///
+/// The output is the `PACK` magic, version 2, the object count, then each
+/// object's type/size header followed by its zlib-compressed body, and finally
+/// a SHA-1 trailer over the entire stream.
+pub fn build(objects: &[(GitObjectType, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    out.extend_from_slice(b"PACK");
+    out.extend_from_slice(&2u32.to_be_bytes());
+    out.extend_from_slice(&(objects.len() as u32).to_be_bytes());
+
+    for (obj_type, body) in objects {
+        out.extend_from_slice(&encode_object_header(obj_type, body.len()));
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(body)
+            .context("Failed to compress packfile object")?;
+        let compressed = encoder
+            .finish()
+            .context("Failed to finish packfile compression")?;
+        out.extend_from_slice(&compressed);
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(&out);
+    out.extend_from_slice(&hasher.finalize());
+    Ok(out)
+}
+
+/// Encodes the packfile object header: type in bits 4-6 of the first byte and a
+/// size split into a 4-bit low nibble followed by 7-bit little-endian groups.
+fn encode_object_header(obj_type: &GitObjectType, size: usize) -> Vec<u8> {
+    let type_code = match obj_type {
+        GitObjectType::Commit => OBJ_COMMIT,
+        GitObjectType::Tree => OBJ_TREE,
+        GitObjectType::Blob => OBJ_BLOB,
+        GitObjectType::Tag => OBJ_TAG,
+    };
+
+    let mut header = Vec::new();
+    let mut byte = (type_code << 4) | (size & 0x0f) as u8;
+    let mut remaining = size >> 4;
+    while remaining != 0 {
+        header.push(byte | 0x80);
+        byte = (remaining & 0x7f) as u8;
+        remaining >>= 7;
+    }
+    header.push(byte);
+    header
+}
+
+/// Verifies the trailing SHA-1 checksum covering the whole stream.
+fn verify_checksum(data: &[u8]) -> Result<()> {
+    let (body, trailer) = data.split_at(data.len() - 20);
+    let mut hasher = Sha1::new();
+    hasher.update(body);
+    if hasher.finalize().as_slice() != trailer {
+        bail!("Packfile checksum mismatch");
+    }
+    Ok(())
+}
+
+/// Maps a packfile type code to its [`GitObjectType`].
+fn object_type(code: u8) -> Result<GitObjectType> {
+    Ok(match code {
+        OBJ_COMMIT => GitObjectType::Commit,
+        OBJ_TREE => GitObjectType::Tree,
+        OBJ_BLOB => GitObjectType::Blob,
+        OBJ_TAG => GitObjectType::Tag,
+        other => bail!("Unsupported packfile object type: {other}"),
+    })
+}
+
+/// Reads a packfile object header, returning `(type, size, bytes_consumed)`.
+fn read_object_header(data: &[u8]) -> Result<(u8, usize, usize)> {
+    let mut byte = *data.first().context("Truncated packfile object header")?;
+    let type_code = (byte >> 4) & 0x07;
+    let mut size = (byte & 0x0f) as usize;
+    let mut shift = 4;
+    let mut consumed = 1;
+    while byte & 0x80 != 0 {
+        byte = *data
+            .get(consumed)
+            .context("Truncated packfile object header")?;
+        size |= ((byte & 0x7f) as usize) << shift;
+        shift += 7;
+        consumed += 1;
+    }
+    Ok((type_code, size, consumed))
+}
+
+/// Reads an ofs-delta negative offset varint, returning `(offset, bytes_consumed)`.
+fn read_offset(data: &[u8]) -> Result<(usize, usize)> {
+    let mut byte = *data.first().context("Truncated ofs-delta offset")?;
+    let mut offset = (byte & 0x7f) as usize;
+    let mut consumed = 1;
+    while byte & 0x80 != 0 {
+        byte = *data.get(consumed).context("Truncated ofs-delta offset")?;
+        offset = ((offset + 1) << 7) | (byte & 0x7f) as usize;
+        consumed += 1;
+    }
+    Ok((offset, consumed))
+}
+
+/// Inflates a zlib stream, returning the bytes and the number of compressed bytes read.
+fn inflate(data: &[u8]) -> Result<(Vec<u8>, usize)> {
+    let mut decoder = ZlibDecoder::new(data);
+    let mut out = Vec::new();
+    decoder
+        .read_to_end(&mut out)
+        .context("Failed to inflate packfile object")?;
+    Ok((out, decoder.total_in() as usize))
+}
+
+/// Reconstructs a delta-encoded object against its base.
+fn apply_delta(base: &[u8], delta: &[u8]) -> Result<Vec<u8>> {
+    let mut pos = 0;
+    let _source_size = read_delta_size(delta, &mut pos)?;
+    let target_size = read_delta_size(delta, &mut pos)?;
+
+    let mut out = Vec::with_capacity(target_size);
+    while pos < delta.len() {
+        let instruction = delta[pos];
+        pos += 1;
+        if instruction & 0x80 != 0 {
+            // Copy from base: low 7 bits select which offset/size bytes follow.
+            let mut offset = 0usize;
+            for i in 0..4 {
+                if instruction & (1 << i) != 0 {
+                    let byte = *delta.get(pos).context("Truncated delta copy offset")?;
+                    offset |= (byte as usize) << (8 * i);
+                    pos += 1;
+                }
+            }
+            let mut size = 0usize;
+            for i in 0..3 {
+                if instruction & (1 << (4 + i)) != 0 {
+                    let byte = *delta.get(pos).context("Truncated delta copy size")?;
+                    size |= (byte as usize) << (8 * i);
+                    pos += 1;
+                }
+            }
+            if size == 0 {
+                size = 0x10000;
+            }
+            let end = offset
+                .checked_add(size)
+                .filter(|&end| end <= base.len())
+                .context("Delta copy out of base bounds")?;
+            out.extend_from_slice(&base[offset..end]);
+        } else {
+            // Insert: the instruction byte is the literal length.
+            let size = instruction as usize;
+            let end = pos
+                .checked_add(size)
+                .filter(|&end| end <= delta.len())
+                .context("Delta insert out of bounds")?;
+            out.extend_from_slice(&delta[pos..end]);
+            pos += size;
+        }
+    }
+
+    if out.len() != target_size {
+        bail!("Delta reconstruction produced wrong length");
+    }
+    Ok(out)
+}
+
+/// Reads a little-endian delta size varint, advancing `pos`.
+fn read_delta_size(delta: &[u8], pos: &mut usize) -> Result<usize> {
+    let mut size = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = *delta.get(*pos).context("Truncated delta size varint")?;
+        *pos += 1;
+        size |= ((byte & 0x7f) as usize) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    Ok(size)
+}
+
+/// Prepends the loose-object header and stores the object, returning its hash.
+fn store_object(obj_type: &GitObjectType, body: &[u8]) -> Result<String> {
+    let mut object = format!("{obj_type} {}\0", body.len()).into_bytes();
+    object.extend_from_slice(body);
+    write_git_object(&object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_delta_copies_and_inserts() {
+        let base = b"hello";
+        // source size 5, target size 8, insert "Hi ", then copy base[0..5].
+        let delta = [0x05, 0x08, 0x03, b'H', b'i', b' ', 0x90, 0x05];
+        assert_eq!(apply_delta(base, &delta).unwrap(), b"Hi hello");
+    }
+
+    #[test]
+    fn apply_delta_rejects_out_of_range_copy() {
+        let base = b"hi";
+        // Copy offset 0, size 5 — past the 2-byte base.
+        let delta = [0x02, 0x05, 0x90, 0x05];
+        assert!(apply_delta(base, &delta).is_err());
+    }
+
+    #[test]
+    fn read_offset_decodes_ofs_delta_varint() {
+        // Two-byte offset varint with the continuation carry applied.
+        let (offset, consumed) = read_offset(&[0x81, 0x00]).unwrap();
+        assert_eq!((offset, consumed), (256, 2));
+    }
+
+    #[test]
+    fn read_object_header_errors_on_truncation() {
+        // Continuation bit set but no following byte.
+        assert!(read_object_header(&[0x80]).is_err());
+    }
+}
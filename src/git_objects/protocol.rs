@@ -0,0 +1 @@
+pub mod pkt_line;
@@ -0,0 +1,105 @@
+use anyhow::{bail, Context, Result};
+
+/// A single frame decoded from a Git pkt-line stream.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PktLine {
+    /// A data packet carrying its payload (length prefix stripped).
+    Data(Vec<u8>),
+    /// A flush-pkt (`0000`).
+    Flush,
+    /// A delimiter (`0001`) or response-end (`0002`) marker.
+    Delim,
+}
+
+/// Encodes a payload as a pkt-line: a 4-byte hex length prefix (covering the
+/// prefix itself) followed by the payload.
+pub fn encode(payload: &[u8]) -> Vec<u8> {
+    let mut line = format!("{:04x}", payload.len() + 4).into_bytes();
+    line.extend_from_slice(payload);
+    line
+}
+
+/// Returns the flush-pkt marker.
+pub fn flush() -> [u8; 4] {
+    *b"0000"
+}
+
+/// Decodes one pkt-line from `buf`, advancing it past the consumed bytes.
+///
+/// Returns `Ok(None)` when `buf` is exhausted, and a [`PktLine`] otherwise.
+pub fn decode(buf: &mut &[u8]) -> Result<Option<PktLine>> {
+    if buf.is_empty() {
+        return Ok(None);
+    }
+    if buf.len() < 4 {
+        bail!("Truncated pkt-line length prefix");
+    }
+    let len = usize::from_str_radix(
+        std::str::from_utf8(&buf[..4]).context("Invalid pkt-line length prefix")?,
+        16,
+    )
+    .context("Invalid pkt-line length prefix")?;
+
+    match len {
+        0 => {
+            *buf = &buf[4..];
+            Ok(Some(PktLine::Flush))
+        }
+        1 | 2 => {
+            *buf = &buf[4..];
+            Ok(Some(PktLine::Delim))
+        }
+        _ => {
+            if len < 4 || len > buf.len() {
+                bail!("Malformed pkt-line length: {len}");
+            }
+            let payload = buf[4..len].to_vec();
+            *buf = &buf[len..];
+            Ok(Some(PktLine::Data(payload)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_prefixes_total_length() {
+        // "hi" plus the 4-byte prefix is six bytes: "0006hi".
+        assert_eq!(encode(b"hi"), b"0006hi");
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let mut stream = encode(b"want 1234\n");
+        stream.extend_from_slice(&flush());
+        stream.extend_from_slice(&encode(b"done\n"));
+
+        let mut buf = stream.as_slice();
+        assert_eq!(
+            decode(&mut buf).unwrap(),
+            Some(PktLine::Data(b"want 1234\n".to_vec()))
+        );
+        assert_eq!(decode(&mut buf).unwrap(), Some(PktLine::Flush));
+        assert_eq!(
+            decode(&mut buf).unwrap(),
+            Some(PktLine::Data(b"done\n".to_vec()))
+        );
+        assert_eq!(decode(&mut buf).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_recognizes_delim_marker() {
+        let mut buf = b"0001".as_slice();
+        assert_eq!(decode(&mut buf).unwrap(), Some(PktLine::Delim));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn decode_rejects_malformed_length() {
+        // Claims a 10-byte packet but only four bytes are present.
+        let mut buf = b"000a".as_slice();
+        assert!(decode(&mut buf).is_err());
+    }
+}
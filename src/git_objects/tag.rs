@@ -0,0 +1,44 @@
+use crate::git_objects::commit::Signature;
+use anyhow::{Context, Result};
+
+/// A parsed annotated-tag object.
+#[derive(Debug)]
+pub struct Tag {
+    pub object: String,
+    pub object_type: String,
+    pub name: String,
+    pub tagger: Option<Signature>,
+    pub message: String,
+}
+
+/// Parses the body of a tag object (the bytes after the `"tag <len>\0"` header).
+pub fn parse_tag(body: &[u8]) -> Result<Tag> {
+    let text = std::str::from_utf8(body).context("Invalid tag encoding")?;
+
+    let (header, message) = text.split_once("\n\n").unwrap_or((text, ""));
+
+    let mut object = None;
+    let mut object_type = None;
+    let mut name = None;
+    let mut tagger = None;
+
+    for line in header.lines() {
+        if let Some(sha) = line.strip_prefix("object ") {
+            object = Some(sha.to_string());
+        } else if let Some(kind) = line.strip_prefix("type ") {
+            object_type = Some(kind.to_string());
+        } else if let Some(tag) = line.strip_prefix("tag ") {
+            name = Some(tag.to_string());
+        } else if let Some(sig) = line.strip_prefix("tagger ") {
+            tagger = Some(Signature::parse(sig)?);
+        }
+    }
+
+    Ok(Tag {
+        object: object.context("Tag missing object")?,
+        object_type: object_type.context("Tag missing type")?,
+        name: name.context("Tag missing name")?,
+        tagger,
+        message: message.to_string(),
+    })
+}
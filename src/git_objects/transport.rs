@@ -0,0 +1,402 @@
+use crate::git_objects::protocol::pkt_line::{self, PktLine};
+use crate::git_objects::{
+    packfile, parse_object, parse_object_header, parse_tree_entries, read_git_object, FileMode,
+    GitObject, GitObjectType,
+};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// A discovered reference from the smart-HTTP ref advertisement.
+#[derive(Debug)]
+pub struct RemoteRef {
+    pub name: String,
+    pub sha1: String,
+}
+
+/// Clones a remote repository over the Git smart-HTTP transport into `target_dir`.
+///
+/// This performs the reference discovery / upload-pack negotiation described by
+/// `git-upload-pack`: it fetches the ref advertisement, asks for every branch tip
+/// with `want` lines, unpacks the returned packfile into `.git/objects` and checks
+/// out the `HEAD` tree to the working directory.
+pub fn clone(url: &str, target_dir: &str) -> Result<()> {
+    let url = url.trim_end_matches('/');
+
+    // Lay out the target repository before populating it.
+    init_repository(target_dir)?;
+
+    // Discover the refs advertised by the remote.
+    let (head_ref, refs) = discover_refs(url)?;
+
+    // Ask for every advertised branch tip and read back the packfile.
+    let pack = fetch_pack(url, &refs)?;
+
+    // Run everything else from inside the freshly created repository so the
+    // existing object helpers resolve `.git` relative to the new working tree.
+    let previous_dir = std::env::current_dir().context("Failed to read current directory")?;
+    std::env::set_current_dir(target_dir)
+        .with_context(|| format!("Failed to enter clone target: {target_dir}"))?;
+
+    let result = (|| -> Result<()> {
+        packfile::unpack(&pack)?;
+        write_refs(&refs)?;
+
+        // Resolve HEAD to a concrete commit and check its tree out.
+        let head_sha = head_ref
+            .as_ref()
+            .and_then(|name| refs.iter().find(|r| &r.name == name))
+            .or_else(|| refs.iter().find(|r| r.name == "refs/heads/master"))
+            .or_else(|| refs.iter().find(|r| r.name.starts_with("refs/heads/")))
+            .map(|r| r.sha1.clone())
+            .context("Remote advertised no branch to check out")?;
+
+        if let Some(name) = &head_ref {
+            fs::write(".git/HEAD", format!("ref: {name}\n")).context("Failed to write HEAD")?;
+        }
+
+        checkout_commit(&head_sha, Path::new("."))?;
+        Ok(())
+    })();
+
+    // Always restore the original working directory, even on error.
+    std::env::set_current_dir(&previous_dir).context("Failed to restore working directory")?;
+    result
+}
+
+/// Creates the `.git` skeleton for a clone target.
+fn init_repository(target_dir: &str) -> Result<()> {
+    let git_dir = Path::new(target_dir).join(".git");
+    fs::create_dir_all(git_dir.join("objects"))
+        .context("Failed to create .git/objects directory")?;
+    fs::create_dir_all(git_dir.join("refs").join("heads"))
+        .context("Failed to create .git/refs/heads directory")?;
+    fs::write(git_dir.join("HEAD"), "ref: refs/heads/master\n").context("Failed to write HEAD")?;
+    Ok(())
+}
+
+/// Issues `GET $URL/info/refs?service=git-upload-pack` and parses the advertisement.
+///
+/// Returns the symbolic target of `HEAD` (if advertised) and every non-peeled ref
+/// other than `HEAD` itself.
+fn discover_refs(url: &str) -> Result<(Option<String>, Vec<RemoteRef>)> {
+    let endpoint = format!("{url}/info/refs?service=git-upload-pack");
+    let body = reqwest::blocking::Client::new()
+        .get(&endpoint)
+        .send()
+        .with_context(|| format!("Failed to GET {endpoint}"))?
+        .error_for_status()
+        .context("Ref discovery request failed")?
+        .bytes()
+        .context("Failed to read ref advertisement")?
+        .to_vec();
+
+    let mut buf = body.as_slice();
+    let mut refs = Vec::new();
+    let mut head_ref = None;
+    let mut first_ref = true;
+
+    while let Some(frame) = pkt_line::decode(&mut buf)? {
+        let line = match frame {
+            PktLine::Data(payload) => payload,
+            PktLine::Flush | PktLine::Delim => continue,
+        };
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim_end_matches('\n');
+
+        // Skip the `# service=git-upload-pack` banner.
+        if text.starts_with('#') {
+            continue;
+        }
+
+        // The first ref line carries NUL-separated capabilities.
+        let (ref_line, caps) = match text.split_once('\0') {
+            Some((l, c)) => (l, Some(c)),
+            None => (text, None),
+        };
+
+        let mut parts = ref_line.splitn(2, ' ');
+        let sha1 = parts.next().unwrap_or_default().to_string();
+        let name = match parts.next() {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        // `HEAD` is advertised first with a `symref=HEAD:refs/heads/...` capability.
+        if first_ref {
+            if let Some(caps) = caps {
+                for cap in caps.split(' ') {
+                    if let Some(rest) = cap.strip_prefix("symref=HEAD:") {
+                        head_ref = Some(rest.to_string());
+                    }
+                }
+            }
+            first_ref = false;
+        }
+
+        // Peeled tag entries (`^{}`) and the `HEAD` pseudo-ref are not branches.
+        if name.ends_with("^{}") || name == "HEAD" {
+            continue;
+        }
+
+        refs.push(RemoteRef { name, sha1 });
+    }
+
+    Ok((head_ref, refs))
+}
+
+/// POSTs a `want`/`done` request to `git-upload-pack` and returns the raw packfile.
+fn fetch_pack(url: &str, refs: &[RemoteRef]) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    let branches = refs.iter().filter(|r| r.name.starts_with("refs/heads/"));
+    for (i, r) in branches.enumerate() {
+        let line = if i == 0 {
+            // Advertise the capabilities we rely on on the first want line.
+            format!("want {} multi_ack_detailed side-band-64k ofs-delta\n", r.sha1)
+        } else {
+            format!("want {}\n", r.sha1)
+        };
+        body.extend_from_slice(&pkt_line::encode(line.as_bytes()));
+    }
+    body.extend_from_slice(&pkt_line::flush());
+    body.extend_from_slice(&pkt_line::encode(b"done\n"));
+
+    let endpoint = format!("{url}/git-upload-pack");
+    let response = reqwest::blocking::Client::new()
+        .post(&endpoint)
+        .header("Content-Type", "application/x-git-upload-pack-request")
+        .body(body)
+        .send()
+        .with_context(|| format!("Failed to POST {endpoint}"))?
+        .error_for_status()
+        .context("upload-pack request failed")?
+        .bytes()
+        .context("Failed to read upload-pack response")?
+        .to_vec();
+
+    // The response is a stream of pkt-lines; everything after the `NAK`/`ACK`
+    // control line is the packfile payload, optionally multiplexed on side-band 1.
+    let mut buf = response.as_slice();
+    let mut pack = Vec::new();
+    while let Some(frame) = pkt_line::decode(&mut buf)? {
+        let line = match frame {
+            PktLine::Data(payload) => payload,
+            PktLine::Flush | PktLine::Delim => continue,
+        };
+        if line.is_empty() {
+            continue;
+        }
+        match line[0] {
+            // side-band channel 1 carries the packfile data.
+            1 => pack.extend_from_slice(&line[1..]),
+            // channels 2 (progress) and 3 (error) are not pack data.
+            2 | 3 => continue,
+            _ => {
+                let text = String::from_utf8_lossy(&line);
+                let text = text.trim_end();
+                if text.starts_with("ACK") || text.starts_with("NAK") {
+                    continue;
+                }
+                // No side-band: the line itself is raw pack data.
+                pack.extend_from_slice(&line);
+            }
+        }
+    }
+
+    if !pack.starts_with(b"PACK") {
+        bail!("upload-pack response did not contain a packfile");
+    }
+    Ok(pack)
+}
+
+/// Writes every discovered branch tip under `.git/refs/heads`.
+fn write_refs(refs: &[RemoteRef]) -> Result<()> {
+    for r in refs {
+        if let Some(branch) = r.name.strip_prefix("refs/heads/") {
+            let path = Path::new(".git/refs/heads").join(branch);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, format!("{}\n", r.sha1))
+                .with_context(|| format!("Failed to write ref {}", r.name))?;
+        }
+    }
+    Ok(())
+}
+
+/// A single cache entry destined for `.git/index`.
+struct IndexEntry {
+    path: std::path::PathBuf,
+    sha1: String,
+    mode: FileMode,
+}
+
+/// Recursively writes the tree of `commit_sha` to `dir`, then records the
+/// checked-out blobs in `.git/index` so the working tree is reported as clean.
+fn checkout_commit(commit_sha: &str, dir: &Path) -> Result<()> {
+    let commit = read_git_object(commit_sha)?;
+    let tree_sha = commit_tree_sha(&commit)?;
+    let mut entries = Vec::new();
+    checkout_tree(&tree_sha, dir, &mut entries)?;
+    write_index(&mut entries)?;
+    Ok(())
+}
+
+/// Extracts the `tree` hash referenced by a commit object.
+fn commit_tree_sha(commit: &[u8]) -> Result<String> {
+    match parse_object(commit)? {
+        GitObject::Commit(commit) => Ok(commit.tree),
+        other => bail!("Expected a commit object, found {}", other.object_type()),
+    }
+}
+
+/// Materialises a tree object and its children into `dir`, appending every
+/// written blob to `index`.
+fn checkout_tree(tree_sha: &str, dir: &Path, index: &mut Vec<IndexEntry>) -> Result<()> {
+    let tree = read_git_object(tree_sha)?;
+    let entries = parse_tree_entries(&tree)?;
+    for entry in entries {
+        let path = dir.join(&entry.name);
+        match entry.object_type {
+            GitObjectType::Tree => {
+                fs::create_dir_all(&path)
+                    .with_context(|| format!("Failed to create directory {}", path.display()))?;
+                checkout_tree(&entry.sha1, &path, index)?;
+            }
+            _ => {
+                let object = read_git_object(&entry.sha1)?;
+                let content = object_body(&object)?;
+                fs::write(&path, content)
+                    .with_context(|| format!("Failed to write file {}", path.display()))?;
+                set_mode(&path, &entry.mode)?;
+                index.push(IndexEntry {
+                    path,
+                    sha1: entry.sha1,
+                    mode: entry.mode,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the collected entries to `.git/index` in the version-2 format so a
+/// freshly cloned tree is reported clean by `git status`.
+fn write_index(entries: &mut [IndexEntry]) -> Result<()> {
+    use crate::git_objects::hex_to_bytes;
+
+    // Index entries are sorted by path, with directory separators ordered as
+    // plain bytes.
+    entries.sort_by(|a, b| index_name(&a.path).cmp(&index_name(&b.path)));
+
+    let mut body = Vec::new();
+    body.extend_from_slice(b"DIRC");
+    body.extend_from_slice(&2u32.to_be_bytes());
+    body.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+
+    for entry in entries.iter() {
+        let name = index_name(&entry.path);
+        let (ctime, mtime, dev, ino, uid, gid, size) = stat_fields(&entry.path)?;
+
+        body.extend_from_slice(&ctime.to_be_bytes());
+        body.extend_from_slice(&0u32.to_be_bytes()); // ctime nanoseconds
+        body.extend_from_slice(&mtime.to_be_bytes());
+        body.extend_from_slice(&0u32.to_be_bytes()); // mtime nanoseconds
+        body.extend_from_slice(&dev.to_be_bytes());
+        body.extend_from_slice(&ino.to_be_bytes());
+        body.extend_from_slice(&index_mode(&entry.mode).to_be_bytes());
+        body.extend_from_slice(&uid.to_be_bytes());
+        body.extend_from_slice(&gid.to_be_bytes());
+        body.extend_from_slice(&size.to_be_bytes());
+        body.extend_from_slice(&hex_to_bytes(&entry.sha1)?);
+
+        let name_bytes = name.as_bytes();
+        let flags = name_bytes.len().min(0xfff) as u16;
+        body.extend_from_slice(&flags.to_be_bytes());
+        body.extend_from_slice(name_bytes);
+
+        // Pad with NUL bytes so the entry length is a multiple of 8, keeping at
+        // least one trailing NUL after the name.
+        let entry_len = 62 + name_bytes.len();
+        let padding = 8 - (entry_len % 8);
+        body.resize(body.len() + padding, 0);
+    }
+
+    let mut hasher = sha1::Sha1::new();
+    sha1::Digest::update(&mut hasher, &body);
+    body.extend_from_slice(&sha1::Digest::finalize(hasher));
+
+    fs::write(".git/index", body).context("Failed to write .git/index")?;
+    Ok(())
+}
+
+/// The repository-relative, slash-separated path recorded for an index entry.
+fn index_name(path: &Path) -> String {
+    path.strip_prefix(".")
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Maps a [`FileMode`] to the 32-bit mode stored in the index.
+fn index_mode(mode: &FileMode) -> u32 {
+    match mode {
+        FileMode::RegularFile => 0o100644,
+        FileMode::ExecutableFile => 0o100755,
+        FileMode::SymbolicLink => 0o120000,
+        FileMode::Directory => 0o040000,
+    }
+}
+
+/// Reads the stat fields recorded in an index entry: `(ctime, mtime, dev, ino,
+/// uid, gid, size)`. Non-stat platforms record zeros for all but the size,
+/// which `git` simply refreshes on first use.
+fn stat_fields(path: &Path) -> Result<(u32, u32, u32, u32, u32, u32, u32)> {
+    let metadata = fs::metadata(path)
+        .with_context(|| format!("Failed to stat {}", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Ok((
+            metadata.ctime() as u32,
+            metadata.mtime() as u32,
+            metadata.dev() as u32,
+            metadata.ino() as u32,
+            metadata.uid(),
+            metadata.gid(),
+            metadata.len() as u32,
+        ))
+    }
+
+    #[cfg(not(unix))]
+    {
+        Ok((0, 0, 0, 0, 0, 0, metadata.len() as u32))
+    }
+}
+
+/// Returns the payload of a loose object, stripping the `"<type> <len>\0"` header.
+fn object_body(object: &[u8]) -> Result<Vec<u8>> {
+    let (_, _, body_start) = parse_object_header(object)?;
+    Ok(object[body_start..].to_vec())
+}
+
+/// Applies the executable bit when checking out an executable blob.
+fn set_mode(path: &Path, mode: &FileMode) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let FileMode::ExecutableFile = mode {
+            let mut perms = fs::metadata(path)?.permissions();
+            perms.set_mode(0o755);
+            fs::set_permissions(path, perms)?;
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, mode);
+    }
+    Ok(())
+}
+
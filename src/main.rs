@@ -21,6 +21,12 @@ enum Command {
         /// Pretty-print the contents of the object
         #[arg(short = 'p', help = "Pretty-print the contents of the object")]
         pretty_print: bool,
+        /// Show the object type
+        #[arg(short = 't', help = "Show the object type")]
+        show_type: bool,
+        /// Show the object size
+        #[arg(short = 's', help = "Show the object size")]
+        show_size: bool,
         /// The SHA-1 hash of the object to display
         #[arg(help = "The SHA-1 hash of the object to display")]
         object_hash: String,
@@ -46,6 +52,13 @@ enum Command {
     },
     /// Create a tree object from the current directory
     WriteTree,
+    /// Clone a repository into a new directory
+    Clone {
+        #[arg(help = "The URL of the repository to clone")]
+        url: String,
+        #[arg(help = "The directory to clone into")]
+        directory: Option<String>,
+    },
     /// Create a commit object
     CommitTree {
         #[arg(help = "The hash of the tree to commit")]
@@ -55,6 +68,11 @@ enum Command {
         #[arg(short = 'm', value_name = "MESSAGE", help = "The commit message")]
         message: String,
     },
+    /// Create a packfile from the objects reachable from a commit or tree
+    PackObjects {
+        #[arg(help = "The commit or tree SHA whose reachable objects to pack")]
+        object_sha: String,
+    },
 }
 
 fn main() -> Result<()> {
@@ -65,9 +83,11 @@ fn main() -> Result<()> {
         }
         Command::CatFile {
             pretty_print,
+            show_type,
+            show_size,
             object_hash,
         } => {
-            commands::cat_file::run(pretty_print, object_hash)?;
+            commands::cat_file::run(pretty_print, show_type, show_size, object_hash)?;
         }
         Command::HashObject { write, file_path } => {
             commands::hash_object::run(write, file_path)?;
@@ -81,6 +101,9 @@ fn main() -> Result<()> {
         Command::WriteTree => {
             commands::write_tree::run()?;
         }
+        Command::Clone { url, directory } => {
+            commands::clone::run(url, directory)?;
+        }
         Command::CommitTree {
             tree_sha,
             parent_sha,
@@ -88,6 +111,9 @@ fn main() -> Result<()> {
         } => {
             commands::commit_tree::run(tree_sha, parent_sha, message)?;
         }
+        Command::PackObjects { object_sha } => {
+            commands::pack_objects::run(object_sha)?;
+        }
     }
     Ok(())
 }